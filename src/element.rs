@@ -1,5 +1,9 @@
 
 use num_traits::FromPrimitive;
+use serde::de::{Deserializer, Error as DeError};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::ffi::CStr;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
@@ -10,7 +14,7 @@ pub type ASN = u32;
 
 #[repr(u32)]
 #[derive(FromPrimitive, Debug)]
-enum ElementType {
+pub enum ElementType {
     Announcement = bgpstream_sys::bgpstream_elem_type_t_BGPSTREAM_ELEM_TYPE_ANNOUNCEMENT,
     PeerState =  bgpstream_sys::bgpstream_elem_type_t_BGPSTREAM_ELEM_TYPE_PEERSTATE,
     Rib =  bgpstream_sys::bgpstream_elem_type_t_BGPSTREAM_ELEM_TYPE_RIB,
@@ -18,8 +22,21 @@ enum ElementType {
     Withdrawl =  bgpstream_sys::bgpstream_elem_type_t_BGPSTREAM_ELEM_TYPE_WITHDRAWAL,
 }
 
+impl ElementType {
+    // The value string bgpstream expects for a `BGPSTREAM_FILTER_TYPE_ELEM_TYPE` filter.
+    pub fn filter_value(&self) -> &'static str {
+        match self {
+            ElementType::Announcement => "announcements",
+            ElementType::Rib => "ribs",
+            ElementType::Withdrawl => "withdrawals",
+            ElementType::PeerState => "peerstates",
+            ElementType::Unknown => "",
+        }
+    }
+}
+
 #[repr(u32)]
-#[derive(FromPrimitive, Debug)]
+#[derive(FromPrimitive, Debug, Serialize, Deserialize)]
 pub enum PeerState {
     Active = bgpstream_sys::bgpstream_elem_peerstate_t_BGPSTREAM_ELEM_PEERSTATE_ACTIVE,
     Clearing = bgpstream_sys::bgpstream_elem_peerstate_t_BGPSTREAM_ELEM_PEERSTATE_CLEARING,
@@ -51,7 +68,7 @@ pub struct Element {
     data: ElementData,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum ElementData {
     Announcement(AnnouncementData),
     Rib(AnnouncementData),
@@ -59,7 +76,7 @@ pub enum ElementData {
     PeerState(PeerData),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AnnouncementData {
     prefix: Prefix,
     next_hop: IpAddr,
@@ -67,18 +84,18 @@ pub struct AnnouncementData {
     communities: CommunitySet,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PeerData {
     old_peer_state: PeerState,
     new_peer_state: PeerState,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WithdrawlData {
     prefix: Prefix,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Prefix {
     addr: IpAddr,
     length: u8,
@@ -157,6 +174,80 @@ impl Element {
     }
 }
 
+impl Element {
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    pub fn peer_addr(&self) -> IpAddr {
+        self.peer_addr
+    }
+
+    pub fn peer_asn(&self) -> ASN {
+        self.peer_asn
+    }
+
+    pub fn collector(&self) -> &str {
+        &self.collector
+    }
+
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    pub fn data(&self) -> &ElementData {
+        &self.data
+    }
+
+    pub fn into_data(self) -> ElementData {
+        self.data
+    }
+}
+
+impl AnnouncementData {
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+
+    pub fn next_hop(&self) -> IpAddr {
+        self.next_hop
+    }
+
+    pub fn as_path(&self) -> &AsPath {
+        &self.as_path
+    }
+
+    pub fn communities(&self) -> &CommunitySet {
+        &self.communities
+    }
+}
+
+impl WithdrawlData {
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+}
+
+impl PeerData {
+    pub fn old_peer_state(&self) -> &PeerState {
+        &self.old_peer_state
+    }
+
+    pub fn new_peer_state(&self) -> &PeerState {
+        &self.new_peer_state
+    }
+}
+
+impl Prefix {
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    pub fn length(&self) -> u8 {
+        self.length
+    }
+}
+
 fn parse_addr(addr : bgpstream_sys::bgpstream_addr_storage_t) -> Result<IpAddr, ElementError> {
     unsafe {
         match bgpstream_sys::bgpstream_ipv2number(addr.version) {
@@ -222,6 +313,281 @@ fn str_from_buf(buf : &[u8]) -> Result<&str, ElementError> {
     return Ok(c_str.to_str()?);
 }   
 
+impl FromStr for Prefix {
+    type Err = ElementError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '/');
+        let addr = parts
+            .next()
+            .ok_or_else(|| ElementError::IpParseError(s.to_string()))?;
+        let length = parts
+            .next()
+            .ok_or_else(|| ElementError::IpParseError(s.to_string()))?;
+        Ok(Prefix {
+            addr: IpAddr::from_str(addr)
+                .map_err(|_| ElementError::IpParseError(addr.to_string()))?,
+            length: u8::from_str(length)?,
+        })
+    }
+}
+
+impl std::fmt::Display for Prefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.addr, self.length)
+    }
+}
+
+impl Serialize for Prefix {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Prefix {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cidr = String::deserialize(deserializer)?;
+        Prefix::from_str(&cidr).map_err(|_| DeError::custom("malformed CIDR prefix"))
+    }
+}
+
+impl Serialize for PathEntry {
+    // A lone hop serializes as a bare integer; a set serializes as a nested array,
+    // mirroring the `{a,b}` syntax bgpstream uses when it prints the AS path.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            PathEntry::As(asn) => serializer.serialize_u32(*asn),
+            PathEntry::Collection(asns) => asns.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PathEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            As(ASN),
+            Collection(Vec<ASN>),
+        }
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::As(asn) => PathEntry::As(asn),
+            Raw::Collection(asns) => PathEntry::Collection(asns),
+        })
+    }
+}
+
+impl ElementData {
+    // The tag emitted under the `type` key of the JSON and bencode encodings.
+    fn type_name(&self) -> &'static str {
+        match self {
+            ElementData::Announcement(_) => "announcement",
+            ElementData::Rib(_) => "rib",
+            ElementData::Withdrawl(_) => "withdrawal",
+            ElementData::PeerState(_) => "peerstate",
+        }
+    }
+}
+
+impl Element {
+    fn unix_timestamp(&self) -> u64 {
+        self.timestamp
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+
+impl Serialize for Element {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("timestamp", &self.unix_timestamp())?;
+        map.serialize_entry("peer", &self.peer_addr.to_string())?;
+        map.serialize_entry("peer_asn", &self.peer_asn)?;
+        map.serialize_entry("collector", &self.collector)?;
+        map.serialize_entry("project", &self.project)?;
+        map.serialize_entry("type", self.data.type_name())?;
+        match &self.data {
+            ElementData::Announcement(a) | ElementData::Rib(a) => {
+                map.serialize_entry("prefix", &a.prefix)?;
+                map.serialize_entry("next_hop", &a.next_hop.to_string())?;
+                map.serialize_entry("as_path", &a.as_path)?;
+                map.serialize_entry("communities", &a.communities)?;
+            }
+            ElementData::Withdrawl(w) => {
+                map.serialize_entry("prefix", &w.prefix)?;
+            }
+            ElementData::PeerState(p) => {
+                map.serialize_entry("old_state", &p.old_peer_state)?;
+                map.serialize_entry("new_state", &p.new_peer_state)?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// A minimal, self-describing record value in the spirit of bencode: integers as
+/// `i<n>e`, byte strings as `<len>:<bytes>`, lists as `l..e`, and dictionaries as
+/// `d..e` with keys emitted in sorted order (a `BTreeMap` keeps them ordered).
+enum Bencode {
+    Int(i64),
+    Bytes(Vec<u8>),
+    List(Vec<Bencode>),
+    Dict(BTreeMap<Vec<u8>, Bencode>),
+}
+
+impl Bencode {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Bencode::Int(n) => {
+                out.push(b'i');
+                out.extend_from_slice(n.to_string().as_bytes());
+                out.push(b'e');
+            }
+            Bencode::Bytes(bytes) => {
+                out.extend_from_slice(bytes.len().to_string().as_bytes());
+                out.push(b':');
+                out.extend_from_slice(bytes);
+            }
+            Bencode::List(items) => {
+                out.push(b'l');
+                for item in items {
+                    item.encode(out);
+                }
+                out.push(b'e');
+            }
+            Bencode::Dict(entries) => {
+                out.push(b'd');
+                for (key, value) in entries {
+                    Bencode::Bytes(key.clone()).encode(out);
+                    value.encode(out);
+                }
+                out.push(b'e');
+            }
+        }
+    }
+}
+
+fn bencode_str(s: &str) -> Bencode {
+    Bencode::Bytes(s.as_bytes().to_vec())
+}
+
+fn bencode_as_path(path: &AsPath) -> Bencode {
+    Bencode::List(
+        path.iter()
+            .map(|entry| match entry {
+                PathEntry::As(asn) => Bencode::Int(*asn as i64),
+                PathEntry::Collection(asns) => {
+                    Bencode::List(asns.iter().map(|a| Bencode::Int(*a as i64)).collect())
+                }
+            })
+            .collect(),
+    )
+}
+
+fn bencode_communities(communities: &CommunitySet) -> Bencode {
+    Bencode::List(
+        communities
+            .iter()
+            .map(|(asn, value)| {
+                Bencode::List(vec![Bencode::Int(*asn as i64), Bencode::Int(*value as i64)])
+            })
+            .collect(),
+    )
+}
+
+impl Element {
+    fn to_bencode_value(&self) -> Bencode {
+        let mut dict = BTreeMap::new();
+        let mut insert = |key: &str, value: Bencode| {
+            dict.insert(key.as_bytes().to_vec(), value);
+        };
+        insert("timestamp", Bencode::Int(self.unix_timestamp() as i64));
+        insert("peer", bencode_str(&self.peer_addr.to_string()));
+        insert("peer_asn", Bencode::Int(self.peer_asn as i64));
+        insert("collector", bencode_str(&self.collector));
+        insert("project", bencode_str(&self.project));
+        insert("type", bencode_str(self.data.type_name()));
+        match &self.data {
+            ElementData::Announcement(a) | ElementData::Rib(a) => {
+                insert(
+                    "prefix",
+                    bencode_str(&format!("{}/{}", a.prefix.addr, a.prefix.length)),
+                );
+                insert("next_hop", bencode_str(&a.next_hop.to_string()));
+                insert("as_path", bencode_as_path(&a.as_path));
+                insert("communities", bencode_communities(&a.communities));
+            }
+            ElementData::Withdrawl(w) => {
+                insert(
+                    "prefix",
+                    bencode_str(&format!("{}/{}", w.prefix.addr, w.prefix.length)),
+                );
+            }
+            ElementData::PeerState(p) => {
+                insert("old_state", bencode_str(&format!("{:?}", p.old_peer_state)));
+                insert("new_state", bencode_str(&format!("{:?}", p.new_peer_state)));
+            }
+        }
+        Bencode::Dict(dict)
+    }
+
+    /// Encode this element as a compact, self-describing bencode record whose
+    /// dictionary keys are the element's field names in sorted order.
+    pub fn to_bencode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.to_bencode_value().encode(&mut out);
+        out
+    }
+
+    /// Encode this element as a JSON object, a convenience wrapper over the
+    /// `Serialize` implementation for `Element` above.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+// Constructors used by the unit tests to build elements without going through the FFI
+// record path; the real code always populates these types via `Element::create`.
+#[cfg(test)]
+impl Element {
+    pub(crate) fn for_test(
+        timestamp: SystemTime,
+        peer_addr: IpAddr,
+        peer_asn: ASN,
+        project: String,
+        collector: String,
+        data: ElementData,
+    ) -> Element {
+        Element { timestamp, peer_addr, peer_asn, project, collector, data }
+    }
+}
+
+#[cfg(test)]
+impl AnnouncementData {
+    pub(crate) fn for_test(
+        prefix: Prefix,
+        next_hop: IpAddr,
+        as_path: AsPath,
+        communities: CommunitySet,
+    ) -> AnnouncementData {
+        AnnouncementData { prefix, next_hop, as_path, communities }
+    }
+}
+
+#[cfg(test)]
+impl WithdrawlData {
+    pub(crate) fn for_test(prefix: Prefix) -> WithdrawlData {
+        WithdrawlData { prefix }
+    }
+}
+
+#[cfg(test)]
+impl PeerData {
+    pub(crate) fn for_test(old_peer_state: PeerState, new_peer_state: PeerState) -> PeerData {
+        PeerData { old_peer_state, new_peer_state }
+    }
+}
+
 impl std::convert::From<std::ffi::FromBytesWithNulError> for ElementError {
     fn from(_e : std::ffi::FromBytesWithNulError) -> Self {
         ElementError::StringParseError
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use element::{AnnouncementData, ElementData, PeerState, Prefix, ASN};
+use stream::{BGPStreamError, Iter};
+
+/// Identifies a single route in the reconstructed table: the advertising peer
+/// (address and ASN) together with the prefix it carries.
+pub type RouteKey = (IpAddr, ASN, Prefix);
+
+/// An uncompressed binary trie over prefix bits, used purely as a longest-prefix-match
+/// index into [`RibTracker`]'s table; the authoritative state lives in that table.
+struct TrieNode {
+    children: [Option<Box<TrieNode>>; 2],
+    keys: Vec<RouteKey>,
+}
+
+impl TrieNode {
+    fn new() -> TrieNode {
+        TrieNode {
+            children: [None, None],
+            keys: vec![],
+        }
+    }
+
+    fn insert(&mut self, bits: &[bool], key: RouteKey) {
+        match bits.split_first() {
+            None => {
+                if !self.keys.contains(&key) {
+                    self.keys.push(key);
+                }
+            }
+            Some((head, rest)) => {
+                let child = self.children[*head as usize]
+                    .get_or_insert_with(|| Box::new(TrieNode::new()));
+                child.insert(rest, key);
+            }
+        }
+    }
+
+    fn remove(&mut self, bits: &[bool], key: &RouteKey) {
+        match bits.split_first() {
+            None => self.keys.retain(|k| k != key),
+            Some((head, rest)) => {
+                if let Some(child) = self.children[*head as usize].as_mut() {
+                    child.remove(rest, key);
+                }
+            }
+        }
+    }
+
+    // Walk the address bits, remembering the most specific node that still holds a
+    // route; the deterministic `min` keeps repeated lookups stable when several peers
+    // advertise the same prefix.
+    fn lookup<'a>(&'a self, bits: &[bool], best: Option<&'a RouteKey>) -> Option<&'a RouteKey> {
+        let best = self.keys.iter().min().or(best);
+        match bits.split_first() {
+            None => best,
+            Some((head, rest)) => match self.children[*head as usize].as_ref() {
+                Some(child) => child.lookup(rest, best),
+                None => best,
+            },
+        }
+    }
+}
+
+/// The reconstructed routing state: the current [`AnnouncementData`] for every
+/// `(peer, asn, prefix)` key, indexed for longest-prefix match by the tries.
+///
+/// RIB dumps and subsequent updates that overlap in time are handled last-write-wins —
+/// re-advertising a `(peer, prefix)` simply overwrites the stored entry — so the table
+/// always reflects the most recently observed state for each route.
+struct Table {
+    entries: HashMap<RouteKey, AnnouncementData>,
+    v4: TrieNode,
+    v6: TrieNode,
+}
+
+impl Table {
+    fn new() -> Table {
+        Table {
+            entries: HashMap::new(),
+            v4: TrieNode::new(),
+            v6: TrieNode::new(),
+        }
+    }
+
+    fn root(&mut self, addr: IpAddr) -> &mut TrieNode {
+        match addr {
+            IpAddr::V4(_) => &mut self.v4,
+            IpAddr::V6(_) => &mut self.v6,
+        }
+    }
+
+    fn insert_route(&mut self, key: RouteKey, data: AnnouncementData) {
+        let bits = prefix_bits(&key.2);
+        self.root(key.2.addr()).insert(&bits, key.clone());
+        self.entries.insert(key, data);
+    }
+
+    fn remove_route(&mut self, key: &RouteKey) {
+        let bits = prefix_bits(&key.2);
+        self.root(key.2.addr()).remove(&bits, key);
+        self.entries.remove(key);
+    }
+
+    fn clear_peer(&mut self, peer_addr: IpAddr, peer_asn: ASN) {
+        let stale: Vec<RouteKey> = self
+            .entries
+            .keys()
+            .filter(|(addr, asn, _)| *addr == peer_addr && *asn == peer_asn)
+            .cloned()
+            .collect();
+        for key in stale {
+            self.remove_route(&key);
+        }
+    }
+
+    // Apply a single element to the table. Announcements and RIB entries insert/update,
+    // withdrawals delete, and a peer transitioning out of `Established` clears everything
+    // it routed.
+    fn apply(&mut self, peer_addr: IpAddr, peer_asn: ASN, data: ElementData) {
+        match data {
+            ElementData::Announcement(a) | ElementData::Rib(a) => {
+                let key = (peer_addr, peer_asn, a.prefix().clone());
+                self.insert_route(key, a);
+            }
+            ElementData::Withdrawl(w) => {
+                let key = (peer_addr, peer_asn, w.prefix().clone());
+                self.remove_route(&key);
+            }
+            ElementData::PeerState(p) => {
+                let left_established = matches!(p.old_peer_state(), PeerState::Established)
+                    && !matches!(p.new_peer_state(), PeerState::Established);
+                if left_established {
+                    self.clear_peer(peer_addr, peer_asn);
+                }
+            }
+        }
+    }
+
+    fn lookup(&self, addr: IpAddr) -> Option<&AnnouncementData> {
+        let root = match addr {
+            IpAddr::V4(_) => &self.v4,
+            IpAddr::V6(_) => &self.v6,
+        };
+        let key = root.lookup(&addr_bits(addr), None)?;
+        self.entries.get(key)
+    }
+}
+
+/// Drives an [`Iter`] and folds the resulting elements into the routing state they
+/// imply: the set of prefixes each peer currently routes, and by what path.
+pub struct RibTracker<'a> {
+    iter: Iter<'a>,
+    table: Table,
+}
+
+impl<'a> RibTracker<'a> {
+    pub fn from_iter(iter: Iter<'a>) -> RibTracker<'a> {
+        RibTracker {
+            iter,
+            table: Table::new(),
+        }
+    }
+
+    /// Pull and apply the next element. Returns `None` once the underlying stream is
+    /// exhausted, or `Some(Err(..))` if a record or element could not be read.
+    pub fn advance(&mut self) -> Option<Result<(), BGPStreamError>> {
+        match self.iter.next()? {
+            Ok(element) => {
+                let peer_addr = element.peer_addr();
+                let peer_asn = element.peer_asn();
+                self.table.apply(peer_addr, peer_asn, element.into_data());
+                Some(Ok(()))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Drain the stream, applying each element, and stop at the first error, which is
+    /// returned. Without an error it only terminates for finite windows; a live stream
+    /// must be stepped with [`RibTracker::advance`] instead.
+    pub fn run(&mut self) -> Result<(), BGPStreamError> {
+        while let Some(result) = self.advance() {
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Longest-prefix-match the address against the current table, returning the route
+    /// for the most specific covering prefix if any peer advertises one.
+    pub fn lookup(&self, addr: IpAddr) -> Option<&AnnouncementData> {
+        self.table.lookup(addr)
+    }
+
+    /// Iterate the full table snapshot as `(RouteKey, AnnouncementData)` pairs.
+    pub fn snapshot(&self) -> impl Iterator<Item = (&RouteKey, &AnnouncementData)> {
+        self.table.entries.iter()
+    }
+}
+
+// The `length` most-significant bits of the prefix address, MSB first.
+fn prefix_bits(prefix: &Prefix) -> Vec<bool> {
+    let mut bits = addr_bits(prefix.addr());
+    bits.truncate(prefix.length() as usize);
+    bits
+}
+
+fn addr_bits(addr: IpAddr) -> Vec<bool> {
+    let octets: Vec<u8> = match addr {
+        IpAddr::V4(v4) => v4.octets().to_vec(),
+        IpAddr::V6(v6) => v6.octets().to_vec(),
+    };
+    let mut bits = Vec::with_capacity(octets.len() * 8);
+    for byte in octets {
+        for shift in (0..8).rev() {
+            bits.push((byte >> shift) & 1 == 1);
+        }
+    }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use element::{PeerData, WithdrawlData};
+    use std::str::FromStr;
+
+    fn peer(addr: &str) -> IpAddr {
+        IpAddr::from_str(addr).unwrap()
+    }
+
+    fn announcement(prefix: &str) -> AnnouncementData {
+        AnnouncementData::for_test(
+            Prefix::from_str(prefix).unwrap(),
+            peer("10.0.0.254"),
+            vec![],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn lookup_returns_most_specific_route() {
+        let mut t = Table::new();
+        let pa = peer("10.0.0.1");
+        let pb = peer("10.0.0.2");
+        // Overlapping-length prefixes from two peers covering the same address space.
+        t.apply(pa, 65001, ElementData::Rib(announcement("192.0.0.0/8")));
+        t.apply(pa, 65001, ElementData::Announcement(announcement("192.0.2.0/24")));
+        t.apply(pb, 65002, ElementData::Announcement(announcement("192.0.0.0/16")));
+
+        assert_eq!(
+            t.lookup(peer("192.0.2.5")).unwrap().prefix(),
+            &Prefix::from_str("192.0.2.0/24").unwrap()
+        );
+        assert_eq!(
+            t.lookup(peer("192.0.1.1")).unwrap().prefix(),
+            &Prefix::from_str("192.0.0.0/16").unwrap()
+        );
+        assert!(t.lookup(peer("10.1.1.1")).is_none());
+    }
+
+    #[test]
+    fn withdrawal_removes_only_the_matching_key() {
+        let mut t = Table::new();
+        let pa = peer("10.0.0.1");
+        let pb = peer("10.0.0.2");
+        t.apply(pa, 65001, ElementData::Announcement(announcement("192.0.2.0/24")));
+        t.apply(pb, 65002, ElementData::Announcement(announcement("192.0.2.0/24")));
+
+        t.apply(
+            pa,
+            65001,
+            ElementData::Withdrawl(WithdrawlData::for_test(
+                Prefix::from_str("192.0.2.0/24").unwrap(),
+            )),
+        );
+
+        let remaining: Vec<RouteKey> = t.entries.keys().cloned().collect();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].0, pb);
+        // The prefix is still reachable through the peer that never withdrew it.
+        assert!(t.lookup(peer("192.0.2.5")).is_some());
+    }
+
+    #[test]
+    fn peer_state_clears_only_on_leaving_established() {
+        let mut t = Table::new();
+        let pa = peer("10.0.0.1");
+        let pb = peer("10.0.0.2");
+        t.apply(pa, 65001, ElementData::Announcement(announcement("192.0.2.0/24")));
+        t.apply(pb, 65002, ElementData::Announcement(announcement("198.51.100.0/24")));
+
+        // A transition that does not leave `Established` must not touch the table.
+        t.apply(
+            pa,
+            65001,
+            ElementData::PeerState(PeerData::for_test(PeerState::Idle, PeerState::Connect)),
+        );
+        assert_eq!(t.entries.len(), 2);
+
+        // Leaving `Established` clears exactly that peer's entries.
+        t.apply(
+            pa,
+            65001,
+            ElementData::PeerState(PeerData::for_test(PeerState::Established, PeerState::Idle)),
+        );
+        assert_eq!(t.entries.len(), 1);
+        assert!(t.entries.keys().all(|(addr, _, _)| *addr == pb));
+    }
+}
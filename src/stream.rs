@@ -1,7 +1,7 @@
 use std::error::Error;
 use std::ffi::CString;
 
-use element::{Element,ElementError};
+use element::{Element,ElementError,ElementType,Prefix,ASN};
 
 pub const FOREVER: u32 = 0;
 
@@ -13,6 +13,7 @@ pub enum BGPStreamError {
     StartFailed,
     RecordGetFailure,
     ElementFailure(ElementError),
+    InvalidDataInterface(String),
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
@@ -28,6 +29,7 @@ pub struct Stream {
     internal: *mut bgpstream_sys::bgpstream_t,
     record: *mut bgpstream_sys::bgpstream_record_t,
     state: StreamState,
+    live: bool,
 }
 
 pub struct Iter<'a> {
@@ -48,6 +50,7 @@ impl Stream {
             internal: stream,
             record: record,
             state: StreamState::New,
+            live: false,
         })
     }
 
@@ -65,6 +68,17 @@ impl Stream {
         Ok(())
     }
 
+    pub fn add_typed_filter(&mut self, filter_type : bgpstream_sys::bgpstream_filter_type_t, value : &str) -> Result<(), BGPStreamError> {
+        if self.state >= StreamState::Started {
+            return Err(BGPStreamError::OperationOutOfOrder("Cannot add filter after running".to_string()));
+        }
+        let value_cstring = CString::new(value)?;
+        unsafe {
+            bgpstream_sys::bgpstream_add_filter(self.internal, filter_type, value_cstring.as_c_str().as_ptr());
+        }
+        Ok(())
+    }
+
     pub fn add_interval_filter(&mut self, begin_time : u32, end_time : u32) -> Result<(), BGPStreamError> {
         if self.state >= StreamState::Started {
             return Err(BGPStreamError::OperationOutOfOrder("Cannot change interval after running".to_string()));
@@ -73,6 +87,63 @@ impl Stream {
         unsafe {
             bgpstream_sys::bgpstream_add_interval_filter(self.internal, begin_time, end_time);
         }
+        // An open-ended interval (`FOREVER`) implies the caller wants to tail live data.
+        if end_time == FOREVER {
+            self.set_live(true)?;
+        }
+        Ok(())
+    }
+
+    /// Configure the stream for blocking/live mode so the iterator keeps waiting for
+    /// new records instead of completing at the end of the requested window.
+    pub fn set_live(&mut self, live : bool) -> Result<(), BGPStreamError> {
+        if self.state >= StreamState::Started {
+            return Err(BGPStreamError::OperationOutOfOrder("Cannot change live mode after running".to_string()));
+        }
+        if live {
+            unsafe {
+                bgpstream_sys::bgpstream_set_live_mode(self.internal);
+            }
+        }
+        self.live = live;
+        Ok(())
+    }
+
+    /// Select the data interface (e.g. `"broker"`, `"singlefile"`) used to discover records.
+    pub fn set_data_interface(&mut self, interface : &str) -> Result<(), BGPStreamError> {
+        if self.state >= StreamState::Started {
+            return Err(BGPStreamError::OperationOutOfOrder("Cannot change data interface after running".to_string()));
+        }
+        let interface_cstring = CString::new(interface)?;
+        unsafe {
+            let id = bgpstream_sys::bgpstream_get_data_interface_id_by_name(self.internal, interface_cstring.as_c_str().as_ptr());
+            if id == 0 {
+                return Err(BGPStreamError::InvalidDataInterface(interface.to_string()));
+            }
+            bgpstream_sys::bgpstream_set_data_interface(self.internal, id);
+        }
+        Ok(())
+    }
+
+    /// Set an interface-specific option (e.g. the broker URL, or a single file path).
+    pub fn set_data_interface_option(&mut self, interface : &str, key : &str, value : &str) -> Result<(), BGPStreamError> {
+        if self.state >= StreamState::Started {
+            return Err(BGPStreamError::OperationOutOfOrder("Cannot change data interface after running".to_string()));
+        }
+        let interface_cstring = CString::new(interface)?;
+        let key_cstring = CString::new(key)?;
+        let value_cstring = CString::new(value)?;
+        unsafe {
+            let id = bgpstream_sys::bgpstream_get_data_interface_id_by_name(self.internal, interface_cstring.as_c_str().as_ptr());
+            if id == 0 {
+                return Err(BGPStreamError::InvalidDataInterface(interface.to_string()));
+            }
+            let option = bgpstream_sys::bgpstream_get_data_interface_option_by_name(self.internal, id, key_cstring.as_c_str().as_ptr());
+            if option.is_null() {
+                return Err(BGPStreamError::InvalidDataInterface(format!("{}: no such option {}", interface, key)));
+            }
+            bgpstream_sys::bgpstream_set_data_interface_option(self.internal, option, value_cstring.as_c_str().as_ptr());
+        }
         Ok(())
     }
 
@@ -92,6 +163,72 @@ impl Stream {
     }
 }
 
+/// Accumulates typed filter state before producing a configured [`Stream`].
+///
+/// Each method maps to a `bgpstream_add_filter` typed-filter call rather than the
+/// free-form string grammar parsed by [`Stream::add_filter`], so mistakes surface
+/// as type errors at compile time instead of `InvalidFilter` at run time.
+pub struct StreamBuilder {
+    filters: Vec<(bgpstream_sys::bgpstream_filter_type_t, String)>,
+    interval: Option<(u32, u32)>,
+}
+
+impl StreamBuilder {
+    pub fn new() -> StreamBuilder {
+        StreamBuilder {
+            filters: vec![],
+            interval: None,
+        }
+    }
+
+    pub fn collector(mut self, collector : &str) -> Self {
+        self.filters.push((bgpstream_sys::bgpstream_filter_type_t_BGPSTREAM_FILTER_TYPE_COLLECTOR, collector.to_string()));
+        self
+    }
+
+    pub fn project(mut self, project : &str) -> Self {
+        self.filters.push((bgpstream_sys::bgpstream_filter_type_t_BGPSTREAM_FILTER_TYPE_PROJECT, project.to_string()));
+        self
+    }
+
+    pub fn record_type(mut self, record_type : ElementType) -> Self {
+        self.filters.push((bgpstream_sys::bgpstream_filter_type_t_BGPSTREAM_FILTER_TYPE_ELEM_TYPE, record_type.filter_value().to_string()));
+        self
+    }
+
+    pub fn prefix(mut self, prefix : Prefix) -> Self {
+        self.filters.push((bgpstream_sys::bgpstream_filter_type_t_BGPSTREAM_FILTER_TYPE_ELEM_PREFIX, prefix.to_string()));
+        self
+    }
+
+    pub fn peer_asn(mut self, peer_asn : ASN) -> Self {
+        self.filters.push((bgpstream_sys::bgpstream_filter_type_t_BGPSTREAM_FILTER_TYPE_ELEM_PEER_ASN, peer_asn.to_string()));
+        self
+    }
+
+    pub fn community(mut self, asn : ASN, value : u16) -> Self {
+        self.filters.push((bgpstream_sys::bgpstream_filter_type_t_BGPSTREAM_FILTER_TYPE_ELEM_COMMUNITY, format!("{}:{}", asn, value)));
+        self
+    }
+
+    pub fn interval(mut self, begin_time : u32, end_time : u32) -> Self {
+        self.interval = Some((begin_time, end_time));
+        self
+    }
+
+    /// Construct a [`Stream`] with every accumulated filter applied.
+    pub fn build(self) -> Result<Stream, BGPStreamError> {
+        let mut stream = Stream::new()?;
+        for (filter_type, value) in self.filters {
+            stream.add_typed_filter(filter_type, &value)?;
+        }
+        if let Some((begin_time, end_time)) = self.interval {
+            stream.add_interval_filter(begin_time, end_time)?;
+        }
+        Ok(stream)
+    }
+}
+
 impl Drop for Stream {
     fn drop(&mut self) {
         if !self.internal.is_null() {
@@ -112,33 +249,45 @@ impl Drop for Stream {
 impl<'a> Iterator for Iter<'a> {
     type Item = Result<Element, BGPStreamError>;
     fn next(&mut self) -> Option<Result<Element, BGPStreamError>> {
-        let current_state = self.stream.state;
-        assert!(current_state >= StreamState::Started);
-        if current_state == StreamState::Complete {
-            return None;
-        }
-        if current_state == StreamState::Started {
-            let ret_code = unsafe{ bgpstream_sys::bgpstream_get_next_record(self.stream.internal, self.stream.record) };
-            if ret_code < 0 {
-                self.stream.state = StreamState::Complete;
-                return Some(Err(BGPStreamError::RecordGetFailure));
-            } else if ret_code == 0 {
-                self.stream.state = StreamState::Complete;
+        // Looping rather than recursing: in live mode a `0` return keeps us polling for
+        // fresh data, and a record with no more elements drops us back to `Started`. Both
+        // cases would otherwise recurse once per poll, which overflows the stack if the
+        // underlying interface ever returns without blocking.
+        loop {
+            let current_state = self.stream.state;
+            assert!(current_state >= StreamState::Started);
+            if current_state == StreamState::Complete {
                 return None;
-            } else {
-                self.stream.state = StreamState::Ongoing;
             }
-        }
-        assert!(self.stream.state == StreamState::Ongoing);
-        let raw_elem = unsafe { bgpstream_sys::bgpstream_record_get_next_elem(self.stream.record) };
-        if raw_elem.is_null() {
-            self.stream.state = StreamState::Started;
-            return self.next();
-        }
-        let elem = Element::create(raw_elem);
-        match elem {
-            Ok(some) => Some(Ok(some)),
-            Err(element_err) => Some(Err(BGPStreamError::ElementFailure(element_err))),
+            if current_state == StreamState::Started {
+                let ret_code = unsafe{ bgpstream_sys::bgpstream_get_next_record(self.stream.internal, self.stream.record) };
+                if ret_code < 0 {
+                    self.stream.state = StreamState::Complete;
+                    return Some(Err(BGPStreamError::RecordGetFailure));
+                } else if ret_code == 0 {
+                    // In live mode a 0 means "no data *yet*", not end of stream: stay in
+                    // `Started` and poll again for fresh updates. In a finite window it
+                    // means the window is exhausted, so we complete.
+                    if self.stream.live {
+                        continue;
+                    }
+                    self.stream.state = StreamState::Complete;
+                    return None;
+                } else {
+                    self.stream.state = StreamState::Ongoing;
+                }
+            }
+            assert!(self.stream.state == StreamState::Ongoing);
+            let raw_elem = unsafe { bgpstream_sys::bgpstream_record_get_next_elem(self.stream.record) };
+            if raw_elem.is_null() {
+                self.stream.state = StreamState::Started;
+                continue;
+            }
+            let elem = Element::create(raw_elem);
+            return match elem {
+                Ok(some) => Some(Ok(some)),
+                Err(element_err) => Some(Err(BGPStreamError::ElementFailure(element_err))),
+            };
         }
     }
 }
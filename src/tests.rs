@@ -20,3 +20,70 @@ fn biggun() {
     }
     assert!(count == 10);
 }
+
+#[cfg(test)]
+use super::element::{AnnouncementData, Element, ElementData, PathEntry, Prefix, WithdrawlData};
+#[cfg(test)]
+use std::net::IpAddr;
+#[cfg(test)]
+use std::str::FromStr;
+#[cfg(test)]
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn bencode_golden_bytes() {
+    let elem = Element::for_test(
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1567756800),
+        IpAddr::from_str("10.0.0.1").unwrap(),
+        65001,
+        "ris".to_string(),
+        "rrc00".to_string(),
+        ElementData::Withdrawl(WithdrawlData::for_test(Prefix::from_str("192.0.2.0/24").unwrap())),
+    );
+    // Dictionary keys are emitted in sorted byte order, integers as `i<n>e`, byte
+    // strings as `<len>:<bytes>`.
+    let expected = b"d9:collector5:rrc004:peer8:10.0.0.18:peer_asni65001e6:prefix12:192.0.2.0/247:project3:ris9:timestampi1567756800e4:type10:withdrawale";
+    assert_eq!(elem.to_bencode(), expected.to_vec());
+}
+
+#[test]
+fn json_announcement_fields() {
+    let elem = Element::for_test(
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1567756800),
+        IpAddr::from_str("10.0.0.1").unwrap(),
+        65001,
+        "ris".to_string(),
+        "rrc00".to_string(),
+        ElementData::Announcement(AnnouncementData::for_test(
+            Prefix::from_str("192.0.2.0/24").unwrap(),
+            IpAddr::from_str("198.51.100.1").unwrap(),
+            vec![PathEntry::As(65001), PathEntry::Collection(vec![65002, 65003])],
+            vec![(65001, 100)],
+        )),
+    );
+    let json = elem.to_json().unwrap();
+    assert!(json.contains("\"type\":\"announcement\""));
+    assert!(json.contains("\"prefix\":\"192.0.2.0/24\""));
+    assert!(json.contains("\"as_path\":[65001,[65002,65003]]"));
+    assert!(json.contains("\"communities\":[[65001,100]]"));
+}
+
+#[test]
+fn prefix_round_trips_through_serde() {
+    for cidr in &["192.0.2.0/24", "2001:db8::/32"] {
+        let prefix = Prefix::from_str(cidr).unwrap();
+        let json = serde_json::to_string(&prefix).unwrap();
+        assert_eq!(json, format!("\"{}\"", cidr));
+        let back: Prefix = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, prefix);
+    }
+}
+
+#[test]
+fn as_path_round_trips_through_serde() {
+    let path: Vec<PathEntry> = vec![PathEntry::As(65001), PathEntry::Collection(vec![65002, 65003])];
+    let json = serde_json::to_string(&path).unwrap();
+    assert_eq!(json, "[65001,[65002,65003]]");
+    let back: Vec<PathEntry> = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&back).unwrap(), json);
+}